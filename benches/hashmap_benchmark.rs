@@ -0,0 +1,222 @@
+//! Benchmarks our `HashMap` against `std::collections::HashMap`, covering insert, insert then
+//! erase, successful lookup, failed lookup and full iteration. Each operation is run against
+//! three integer key distributions so that weaknesses in hash bucketing show up:
+//! - `sequential`: `0..N`, low-bit-heavy.
+//! - `shifted`: `0..N` shifted into the top bits, high-bit-heavy.
+//! - `random`: keys drawn from a fixed-seed PRNG.
+//!
+//! Run with `cargo bench`.
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hashmap::HashMap as OurHashMap;
+use std::collections::HashMap as StdHashMap;
+
+const ELEMENT_COUNT: usize = 10_000;
+
+#[derive(Clone, Copy)]
+enum Distribution {
+    Sequential,
+    Shifted,
+    Random,
+}
+
+impl Distribution {
+    fn name(self) -> &'static str {
+        match self {
+            Distribution::Sequential => "sequential",
+            Distribution::Shifted => "shifted",
+            Distribution::Random => "random",
+        }
+    }
+
+    fn keys(self, count: usize) -> Vec<u64> {
+        match self {
+            Distribution::Sequential => (0..count as u64).collect(),
+            Distribution::Shifted => (0..count as u64).map(|i| i << 48).collect(),
+            Distribution::Random => {
+                // Fixed-seed xorshift64 so runs are reproducible without pulling in a `rand` dependency.
+                let mut state = 0x2545_f491_4f6c_dd1d_u64;
+                (0..count)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        state
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+const DISTRIBUTIONS: [Distribution; 3] = [
+    Distribution::Sequential,
+    Distribution::Shifted,
+    Distribution::Random,
+];
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+    for distribution in DISTRIBUTIONS {
+        let keys = distribution.keys(ELEMENT_COUNT);
+
+        group.bench_with_input(
+            BenchmarkId::new("ours", distribution.name()),
+            &keys,
+            |b, keys| {
+                b.iter(|| {
+                    let mut map = OurHashMap::new();
+                    for &key in keys {
+                        map.insert(key, key);
+                    }
+                    black_box(map)
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("std", distribution.name()),
+            &keys,
+            |b, keys| {
+                b.iter(|| {
+                    let mut map = StdHashMap::new();
+                    for &key in keys {
+                        map.insert(key, key);
+                    }
+                    black_box(map)
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_insert_then_erase(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert_then_erase");
+    for distribution in DISTRIBUTIONS {
+        let keys = distribution.keys(ELEMENT_COUNT);
+
+        group.bench_with_input(
+            BenchmarkId::new("ours", distribution.name()),
+            &keys,
+            |b, keys| {
+                b.iter(|| {
+                    let mut map = OurHashMap::new();
+                    for &key in keys {
+                        map.insert(key, key);
+                    }
+                    for &key in keys {
+                        black_box(map.remove(&key));
+                    }
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("std", distribution.name()),
+            &keys,
+            |b, keys| {
+                b.iter(|| {
+                    let mut map = StdHashMap::new();
+                    for &key in keys {
+                        map.insert(key, key);
+                    }
+                    for &key in keys {
+                        black_box(map.remove(&key));
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_successful_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("successful_lookup");
+    for distribution in DISTRIBUTIONS {
+        let keys = distribution.keys(ELEMENT_COUNT);
+
+        let mut ours = OurHashMap::new();
+        let mut std_map = StdHashMap::new();
+        for &key in &keys {
+            ours.insert(key, key);
+            std_map.insert(key, key);
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new("ours", distribution.name()),
+            &keys,
+            |b, keys| b.iter(|| keys.iter().for_each(|key| { black_box(ours.get(key)); })),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("std", distribution.name()),
+            &keys,
+            |b, keys| b.iter(|| keys.iter().for_each(|key| { black_box(std_map.get(key)); })),
+        );
+    }
+    group.finish();
+}
+
+fn bench_failed_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("failed_lookup");
+    for distribution in DISTRIBUTIONS {
+        // The distribution's next `ELEMENT_COUNT` keys, none of which are inserted into the maps.
+        let mut keys = distribution.keys(2 * ELEMENT_COUNT);
+        let missing = keys.split_off(ELEMENT_COUNT);
+
+        let mut ours = OurHashMap::new();
+        let mut std_map = StdHashMap::new();
+        for &key in &keys {
+            ours.insert(key, key);
+            std_map.insert(key, key);
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new("ours", distribution.name()),
+            &missing,
+            |b, missing| b.iter(|| missing.iter().for_each(|key| { black_box(ours.get(key)); })),
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("std", distribution.name()),
+            &missing,
+            |b, missing| {
+                b.iter(|| missing.iter().for_each(|key| { black_box(std_map.get(key)); }))
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_iteration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iteration");
+    for distribution in DISTRIBUTIONS {
+        let keys = distribution.keys(ELEMENT_COUNT);
+
+        let mut ours = OurHashMap::new();
+        let mut std_map = StdHashMap::new();
+        for &key in &keys {
+            ours.insert(key, key);
+            std_map.insert(key, key);
+        }
+
+        group.bench_with_input(BenchmarkId::new("ours", distribution.name()), &(), |b, _| {
+            b.iter(|| ours.iter().for_each(|pair| { black_box(pair); }))
+        });
+
+        group.bench_with_input(BenchmarkId::new("std", distribution.name()), &(), |b, _| {
+            b.iter(|| std_map.iter().for_each(|pair| { black_box(pair); }))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_insert,
+    bench_insert_then_erase,
+    bench_successful_lookup,
+    bench_failed_lookup,
+    bench_iteration,
+);
+criterion_main!(benches);
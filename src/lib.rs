@@ -1,69 +1,105 @@
-//! Taken from this [great article](https://betterprogramming.pub/implementing-a-hashmap-in-rust-35d055b5ac2b) with some small improvements:
-//! - `hash_key` only requires a reference to `K`.
-//! - `get` and `remove` take a reference to `K`.
+//! A `HashMap` built on open addressing with quadratic probing, loosely inspired by this
+//! [article](https://betterprogramming.pub/implementing-a-hashmap-in-rust-35d055b5ac2b):
+//! - Resizes itself based on a load factor, like `std`'s `DefaultResizePolicy`.
+//! - Hashing is pluggable through a `BuildHasher` type parameter, defaulting to `RandomState`.
+//! - `get`/`remove` accept any borrowed form of the key (`K: Borrow<Q>`), so e.g. a
+//!   `HashMap<String, V>` can be queried with `&str`. `entry` takes an owned key.
 //! - `K` should only implement `Hash` and `PartialEq`, which allows for a greater set of types to be used as keys.
 //! - `V` has no bounds.
 //! - Places that required an owned value of `V` use `std::mem::replace` instead (typically when returning the old value on an `insert` with an existing key).
 use std::{
-    collections::hash_map::DefaultHasher,
+    borrow::Borrow,
+    collections::hash_map::RandomState,
     fmt::Debug,
-    hash::{Hash, Hasher},
+    hash::{BuildHasher, Hash},
 };
 
-// TODO - Use a prime?
-const DEFAULT_MAX_SIZE: u64 = 256;
+/// Capacity used for an empty map created with `new()`. Must be a power of two.
+const INITIAL_CAPACITY: usize = 32;
 
-fn hash_key<K: Hash>(key: K) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    key.hash(&mut hasher);
-    hasher.finish()
+/// Above this load factor (`(size + tombstones) / capacity`), the backing store is rehashed;
+/// `resize` doubles it only when the live entries alone cross the threshold.
+/// Mirrors std's `DefaultResizePolicy`, which resizes once the map is ~90.9% full
+/// (i.e. `size * 11 >= capacity * 10`). Tombstones count toward the load factor so a table
+/// full of deletions still gets a chance to reclaim them on the next resize.
+fn should_resize(curr_size: usize, capacity: usize) -> bool {
+    curr_size as u64 * 11 >= capacity as u64 * 10
 }
 
-pub struct KeyValue<K, V>
-where
-    K: PartialEq,
-{
-    key: K,
-    value: V,
-    next: Option<Box<KeyValue<K, V>>>,
+/// Smallest power-of-two capacity that can hold `n` entries without crossing the
+/// resize threshold, floored at `INITIAL_CAPACITY`.
+fn capacity_for(n: usize) -> usize {
+    if n == 0 {
+        return INITIAL_CAPACITY;
+    }
+    // Smallest `capacity` such that `n * 11 < capacity * 10`.
+    let needed = (n * 11 / 10) + 1;
+    needed.next_power_of_two().max(INITIAL_CAPACITY)
 }
 
-impl<K, V> Debug for KeyValue<K, V>
-where
-    K: Hash + PartialEq + Debug,
-    V: Debug,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{{ {:?}: {:?} - next: {:?} }}",
-            self.key, self.value, self.next
-        )
+/// Triangular-number quadratic probe sequence (`(h + i*(i+1)/2) & mask`), which is guaranteed
+/// to visit every slot of a power-of-two table before repeating.
+struct Probe {
+    hash: u64,
+    mask: u64,
+    i: u64,
+}
+
+impl Probe {
+    fn new(hash: u64, mask: u64) -> Self {
+        Self { hash, mask, i: 0 }
+    }
+}
+
+impl Iterator for Probe {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let idx = self.hash.wrapping_add(self.i * (self.i + 1) / 2) & self.mask;
+        self.i += 1;
+        Some(idx as usize)
     }
 }
 
-impl<K, V> KeyValue<K, V>
+/// A single slot of a `HashMap`'s backing store.
+enum Slot<K, V> {
+    Empty,
+    /// A tombstone left behind by `remove`, so later probes keep looking past it.
+    Deleted,
+    Full { key: K, value: V, hash: u64 },
+}
+
+impl<K, V> Debug for Slot<K, V>
 where
-    K: PartialEq,
+    K: Debug,
+    V: Debug,
 {
-    pub fn new(key: K, value: V) -> Self {
-        Self {
-            key,
-            value,
-            next: None,
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Slot::Full { key, value, .. } => write!(f, "{key:?}: {value:?}"),
+            Slot::Empty | Slot::Deleted => write!(f, "_"),
         }
     }
 }
 
-pub struct HashMap<K, V>
+/// Where a probe for a key ended up: an existing entry, or the slot it should be written to
+/// (the first `Empty` or `Deleted` slot on the probe sequence).
+enum SlotSearch {
+    Occupied(usize),
+    Vacant(usize),
+}
+
+pub struct HashMap<K, V, S = RandomState>
 where
     K: Hash + PartialEq,
 {
     curr_size: usize,
-    array: [Option<KeyValue<K, V>>; DEFAULT_MAX_SIZE as usize],
+    tombstones: usize,
+    array: Vec<Slot<K, V>>,
+    hash_builder: S,
 }
 
-impl<K, V> Debug for HashMap<K, V>
+impl<K, V, S> Debug for HashMap<K, V, S>
 where
     K: Hash + PartialEq + Debug,
     V: Debug,
@@ -72,118 +108,600 @@ where
         let occupied = self
             .array
             .iter()
-            .filter_map(|v| v.as_ref())
+            .filter(|slot| matches!(slot, Slot::Full { .. }))
             .collect::<Vec<_>>();
         write!(f, "{occupied:?}")
     }
 }
 
-impl<K, V> HashMap<K, V>
+impl<K, V, S> Default for HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V> HashMap<K, V, RandomState>
 where
     K: Hash + PartialEq,
 {
-    fn insert_new_value(&mut self, key: K, value: V, position: usize) {
-        let new_entry = KeyValue::new(key, value);
-        self.array[position].replace(new_entry);
-        self.curr_size += 1;
+    /// Creates an empty `HashMap` with `INITIAL_CAPACITY` slots.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
     }
 
-    /// Traverse the linked list until we either find the value and update it, or append the list with the new value.
-    fn update_or_link_new_val(&mut self, key: K, value: V, position: usize) -> Option<V> {
-        let mut current_kv = self.array[position].as_mut().unwrap();
-        if current_kv.key == key {
-            return Some(std::mem::replace(&mut current_kv.value, value));
+    /// Creates an empty `HashMap` with enough room for `capacity` elements without
+    /// triggering a resize, rounded up to the next power of two.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::default())
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    /// Creates an empty `HashMap` which will use `hasher` to hash keys.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_capacity_and_hasher(0, hasher)
+    }
+
+    /// Creates an empty `HashMap` with enough room for `capacity` elements without
+    /// triggering a resize, which will use `hasher` to hash keys.
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let capacity = capacity_for(capacity);
+        Self {
+            curr_size: 0,
+            tombstones: 0,
+            array: (0..capacity).map(|_| Slot::Empty).collect(),
+            hash_builder: hasher,
         }
+    }
+
+    fn hash_key<Q>(&self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        self.hash_builder.hash_one(key)
+    }
+
+    /// Probes for `key`, returning the matching slot or the first free slot on the sequence.
+    fn find_slot<Q>(&self, hash: u64, key: &Q) -> SlotSearch
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        let mask = self.array.len() as u64 - 1;
+        let mut first_free = None;
 
-        while current_kv.next.is_some() {
-            let node = current_kv.next.as_mut().unwrap();
-            if node.key == key {
-                return Some(std::mem::replace(&mut node.value, value));
+        for idx in Probe::new(hash, mask) {
+            match &self.array[idx] {
+                Slot::Empty => return SlotSearch::Vacant(first_free.unwrap_or(idx)),
+                Slot::Deleted => {
+                    if first_free.is_none() {
+                        first_free = Some(idx);
+                    }
+                }
+                Slot::Full {
+                    key: k, hash: h, ..
+                } => {
+                    if *h == hash && k.borrow() == key {
+                        return SlotSearch::Occupied(idx);
+                    }
+                }
             }
-            current_kv = node;
         }
+        unreachable!("a power-of-two table always has a free slot on its probe sequence")
+    }
 
-        // Append the new entry at the end of the linked list.
-        current_kv.next.replace(KeyValue::new(key, value).into());
-        self.curr_size += 1;
-        None
+    /// Rehashes every live entry using its cached hash, dropping all tombstones in the process.
+    /// Only doubles the backing store if `curr_size` alone (ignoring tombstones) needs the extra
+    /// room; otherwise rehashes into a table of the same size, so tombstone churn from
+    /// insert/remove cycles reclaims space instead of growing the map forever.
+    fn resize(&mut self) {
+        let capacity = self.array.len();
+        let new_capacity = if should_resize(self.curr_size, capacity) {
+            capacity * 2
+        } else {
+            capacity
+        };
+        let mask = new_capacity as u64 - 1;
+        let old = std::mem::replace(
+            &mut self.array,
+            (0..new_capacity).map(|_| Slot::Empty).collect(),
+        );
+        self.tombstones = 0;
+
+        for slot in old {
+            if let Slot::Full { key, value, hash } = slot {
+                let idx = Probe::new(hash, mask)
+                    .find(|&idx| matches!(self.array[idx], Slot::Empty))
+                    .unwrap();
+                self.array[idx] = Slot::Full { key, value, hash };
+            }
+        }
     }
 
     /// Insert a key-value pair into the hashmap.
     /// Returns `None` if the value didnâ€™t exist, or returns the old value if the key was present.
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        let hash = hash_key(&key);
-        let position = (hash % DEFAULT_MAX_SIZE) as usize;
-        if self.array[position].is_some() {
-            self.update_or_link_new_val(key, value, position)
-        } else {
-            self.insert_new_value(key, value, position);
-            None
+        if should_resize(self.curr_size + self.tombstones + 1, self.array.len()) {
+            self.resize();
         }
-    }
 
-    /// Get the value for a given key. Returns the value if it exists, or `None` otherwise.
-    pub fn get(&self, key: &K) -> Option<&V> {
-        let hash_val = hash_key(key);
-        let position = (hash_val % DEFAULT_MAX_SIZE) as usize;
-
-        if let Some(mut kv) = self.array[position].as_ref() {
-            if &kv.key == key {
-                return Some(&kv.value);
-            }
-            while let Some(node) = kv.next.as_ref() {
-                if &node.key == key {
-                    return Some(&node.value);
+        let hash = self.hash_key(&key);
+        match self.find_slot(hash, &key) {
+            SlotSearch::Occupied(idx) => match &mut self.array[idx] {
+                Slot::Full { value: old, .. } => Some(std::mem::replace(old, value)),
+                _ => unreachable!(),
+            },
+            SlotSearch::Vacant(idx) => {
+                if matches!(self.array[idx], Slot::Deleted) {
+                    self.tombstones -= 1;
                 }
-                kv = node;
+                self.array[idx] = Slot::Full { key, value, hash };
+                self.curr_size += 1;
+                None
             }
         }
-        None
+    }
+
+    /// Get the value for a given key. Returns the value if it exists, or `None` otherwise.
+    ///
+    /// The key may be any borrowed form of the map's key type, e.g. `&str` for a
+    /// `HashMap<String, V>`, as long as `Hash` and `PartialEq` agree with the owned form.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let hash = self.hash_key(key);
+        match self.find_slot(hash, key) {
+            SlotSearch::Occupied(idx) => match &self.array[idx] {
+                Slot::Full { value, .. } => Some(value),
+                _ => unreachable!(),
+            },
+            SlotSearch::Vacant(_) => None,
+        }
     }
 
     /// Removes the key-value pair from the map for a given key.
     /// Returns the value if that key existed, `None` otherwise.
-    pub fn remove(&mut self, key: &K) -> Option<V> {
-        let hash = hash_key(key);
-        let position = (hash % DEFAULT_MAX_SIZE) as usize;
-
-        if let Some(mut kv) = self.array[position].as_mut() {
-            if &kv.key == key {
+    ///
+    /// The key may be any borrowed form of the map's key type, e.g. `&str` for a
+    /// `HashMap<String, V>`, as long as `Hash` and `PartialEq` agree with the owned form.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + PartialEq + ?Sized,
+    {
+        let hash = self.hash_key(key);
+        match self.find_slot(hash, key) {
+            SlotSearch::Occupied(idx) => {
                 self.curr_size -= 1;
-                if let Some(next) = kv.next.take() {
-                    return self.array[position].replace(*next).map(|kv| kv.value);
+                self.tombstones += 1;
+                match std::mem::replace(&mut self.array[idx], Slot::Deleted) {
+                    Slot::Full { value, .. } => Some(value),
+                    _ => unreachable!(),
                 }
-                return self.array[position].take().map(|kv| kv.value);
-            }
-            while let Some(node) = kv.next.as_mut() {
-                if &node.key == key {
-                    self.curr_size -= 1;
-                    // Link the deleted node `next` node to the previous node.
-                    if let Some(next) = node.next.take() {
-                        return kv.next.replace(next).map(|kv| kv.value);
-                    } else {
-                        return kv.next.take().map(|kv| kv.value);
-                    }
-                }
-                kv = kv.next.as_mut().unwrap();
             }
+            SlotSearch::Vacant(_) => None,
         }
-        None
     }
 
     /// Clear the hashmap.
     pub fn clear(&mut self) {
-        self.array = [Self::INIT; DEFAULT_MAX_SIZE as usize];
+        for slot in self.array.iter_mut() {
+            *slot = Slot::Empty;
+        }
         self.curr_size = 0;
+        self.tombstones = 0;
     }
 
-    /// Declaring it as `const` to avoid the requirement for `KeyValue<K, V>` to implement `Copy`,
-    const INIT: Option<KeyValue<K, V>> = None;
+    /// Gets the given key's corresponding entry in the map for in-place insert-or-update,
+    /// probing for its slot only once.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        if should_resize(self.curr_size + self.tombstones + 1, self.array.len()) {
+            self.resize();
+        }
 
-    pub fn new() -> Self {
-        Self {
-            curr_size: 0,
-            array: [Self::INIT; DEFAULT_MAX_SIZE as usize],
+        let hash = self.hash_key(&key);
+        match self.find_slot(hash, &key) {
+            SlotSearch::Occupied(index) => Entry::Occupied(OccupiedEntry { index, map: self }),
+            SlotSearch::Vacant(index) => Entry::Vacant(VacantEntry {
+                key,
+                hash,
+                index,
+                map: self,
+            }),
+        }
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.array.iter(),
+        }
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, with mutable references
+    /// to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            inner: self.array.iter_mut(),
+        }
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// An iterator visiting all values mutably, in arbitrary order.
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+
+    /// Clears the map, returning an iterator over the removed key-value pairs.
+    pub fn drain(&mut self) -> Drain<K, V> {
+        let capacity = self.array.len();
+        let array = std::mem::replace(
+            &mut self.array,
+            (0..capacity).map(|_| Slot::Empty).collect(),
+        );
+        self.curr_size = 0;
+        self.tombstones = 0;
+        Drain {
+            inner: array.into_iter(),
+        }
+    }
+}
+
+/// An iterator over the key-value pairs of a [`HashMap`], obtained from [`HashMap::iter`].
+pub struct Iter<'a, K, V> {
+    inner: std::slice::Iter<'a, Slot<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.inner.by_ref() {
+            if let Slot::Full { key, value, .. } = slot {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the key-value pairs of a [`HashMap`] with mutable values, obtained from
+/// [`HashMap::iter_mut`].
+pub struct IterMut<'a, K, V> {
+    inner: std::slice::IterMut<'a, Slot<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.inner.by_ref() {
+            if let Slot::Full { key, value, .. } = slot {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for IterMut<'_, K, V> {}
+
+/// An iterator over the keys of a [`HashMap`], obtained from [`HashMap::keys`].
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of a [`HashMap`], obtained from [`HashMap::values`].
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for Values<'_, K, V> {}
+
+/// An iterator over mutable values of a [`HashMap`], obtained from [`HashMap::values_mut`].
+pub struct ValuesMut<'a, K, V> {
+    inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for ValuesMut<'_, K, V> {}
+
+/// A consuming iterator over the key-value pairs of a [`HashMap`], obtained from its
+/// [`IntoIterator`] implementation.
+pub struct IntoIter<K, V> {
+    inner: std::vec::IntoIter<Slot<K, V>>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.inner.by_ref() {
+            if let Slot::Full { key, value, .. } = slot {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for IntoIter<K, V> {}
+
+/// A draining iterator over the key-value pairs of a [`HashMap`], obtained from
+/// [`HashMap::drain`]. The map is already empty by the time this iterator is produced; iterating
+/// it only consumes the removed entries.
+pub struct Drain<K, V> {
+    inner: std::vec::IntoIter<Slot<K, V>>,
+}
+
+impl<K, V> Iterator for Drain<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in self.inner.by_ref() {
+            if let Slot::Full { key, value, .. } = slot {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V> std::iter::FusedIterator for Drain<K, V> {}
+
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, S> IntoIterator for HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.array.into_iter(),
+        }
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        let mut map = Self::with_capacity_and_hasher(iter.size_hint().0, S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for HashMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// A view into a single entry in a [`HashMap`], obtained from [`HashMap::entry`].
+pub enum Entry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    /// Ensures a value is in the entry by inserting `default` if empty, and returns a mutable
+    /// reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of `default` if empty, and
+    /// returns a mutable reference to the value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any potential insert.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// A view into an occupied entry in a [`HashMap`]. Part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    index: usize,
+    map: &'a mut HashMap<K, V, S>,
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    /// Gets a reference to the key for this entry.
+    pub fn key(&self) -> &K {
+        match &self.map.array[self.index] {
+            Slot::Full { key, .. } => key,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Gets a reference to the value for this entry.
+    pub fn get(&self) -> &V {
+        match &self.map.array[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Gets a mutable reference to the value for this entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        match &mut self.map.array[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Converts the entry into a mutable reference tied to the map's lifetime.
+    pub fn into_mut(self) -> &'a mut V {
+        match &mut self.map.array[self.index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replaces the value for this entry, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+}
+
+/// A view into a vacant entry in a [`HashMap`]. Part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    key: K,
+    hash: u64,
+    index: usize,
+    map: &'a mut HashMap<K, V, S>,
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    /// Gets a reference to the key that would be used when inserting a value through this entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Writes the entry's slot and returns a mutable reference to the newly inserted value.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let VacantEntry {
+            key,
+            hash,
+            index,
+            map,
+        } = self;
+
+        if matches!(map.array[index], Slot::Deleted) {
+            map.tombstones -= 1;
+        }
+        map.array[index] = Slot::Full { key, value, hash };
+        map.curr_size += 1;
+
+        match &mut map.array[index] {
+            Slot::Full { value, .. } => value,
+            _ => unreachable!(),
         }
     }
 }
@@ -191,7 +709,6 @@ where
 // TODO - Add some tests (use some leetcode tests)
 //      - Insert, get, update (insert a new value with an existing key).
 //      - Insert keys whose hashes collide.
-//      - Bench insert, get, delete, update against original impl
 #[cfg(test)]
 mod tests {
     use super::HashMap;
@@ -212,6 +729,165 @@ mod tests {
         println!("HashMap: {hashmap:?}");
     }
 
+    #[test]
+    fn grows_past_initial_capacity() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::new();
+        for i in 0..1000 {
+            hashmap.insert(i, i * 2);
+        }
+        for i in 0..1000 {
+            assert_eq!(hashmap.get(&i), Some(&(i * 2)));
+        }
+    }
+
+    #[test]
+    fn with_capacity_rounds_up_to_power_of_two() {
+        let hashmap: HashMap<i32, i32> = HashMap::with_capacity(10);
+        assert_eq!(hashmap.array.len(), 32);
+    }
+
+    #[test]
+    fn with_hasher_uses_the_given_build_hasher() {
+        use std::collections::hash_map::RandomState;
+
+        let mut hashmap: HashMap<&str, i32, RandomState> = HashMap::with_hasher(RandomState::new());
+        hashmap.insert("guimauve", 1);
+        assert_eq!(hashmap.get(&"guimauve"), Some(&1));
+    }
+
+    #[test]
+    fn entry_or_insert_inserts_and_reuses() {
+        let mut hashmap: HashMap<&str, i32> = HashMap::new();
+        *hashmap.entry("guimauve").or_insert(0) += 1;
+        *hashmap.entry("guimauve").or_insert(0) += 1;
+        assert_eq!(hashmap.get(&"guimauve"), Some(&2));
+    }
+
+    #[test]
+    fn entry_and_modify_only_touches_occupied() {
+        let mut hashmap: HashMap<&str, i32> = HashMap::new();
+        hashmap
+            .entry("guimauve")
+            .and_modify(|v| *v += 1)
+            .or_insert(1);
+        hashmap
+            .entry("guimauve")
+            .and_modify(|v| *v += 1)
+            .or_insert(1);
+        assert_eq!(hashmap.get(&"guimauve"), Some(&2));
+    }
+
+    #[test]
+    fn entry_or_insert_with_is_lazy() {
+        let mut hashmap: HashMap<&str, i32> = HashMap::new();
+        let mut calls = 0;
+        hashmap.entry("guimauve").or_insert_with(|| {
+            calls += 1;
+            1
+        });
+        hashmap.entry("guimauve").or_insert_with(|| {
+            calls += 1;
+            1
+        });
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn iter_visits_every_pair_including_collisions() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::new();
+        for i in 0..50 {
+            hashmap.insert(i, i * 2);
+        }
+        let mut pairs: Vec<_> = hashmap.iter().map(|(&k, &v)| (k, v)).collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, (0..50).map(|i| (i, i * 2)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn iter_mut_can_update_every_value() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::new();
+        for i in 0..50 {
+            hashmap.insert(i, i);
+        }
+        for (_, value) in hashmap.iter_mut() {
+            *value += 1;
+        }
+        for i in 0..50 {
+            assert_eq!(hashmap.get(&i), Some(&(i + 1)));
+        }
+    }
+
+    #[test]
+    fn keys_and_values_match_iter() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::new();
+        hashmap.insert(1, 10);
+        hashmap.insert(2, 20);
+
+        let mut keys: Vec<_> = hashmap.keys().copied().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, vec![1, 2]);
+
+        let mut values: Vec<_> = hashmap.values().copied().collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![10, 20]);
+    }
+
+    #[test]
+    fn into_iter_consumes_the_map() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::new();
+        hashmap.insert(1, 10);
+        hashmap.insert(2, 20);
+
+        let mut pairs: Vec<_> = hashmap.into_iter().collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(1, 10), (2, 20)]);
+    }
+
+    #[test]
+    fn drain_empties_the_map_and_yields_every_pair() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::new();
+        hashmap.insert(1, 10);
+        hashmap.insert(2, 20);
+
+        let mut drained: Vec<_> = hashmap.drain().collect();
+        drained.sort_unstable();
+        assert_eq!(drained, vec![(1, 10), (2, 20)]);
+        assert_eq!(hashmap.get(&1), None);
+        assert_eq!(hashmap.curr_size, 0);
+    }
+
+    #[test]
+    fn from_iterator_and_extend() {
+        let mut hashmap: HashMap<i32, i32> = (0..10).map(|i| (i, i * 2)).collect();
+        hashmap.extend((10..20).map(|i| (i, i * 2)));
+
+        for i in 0..20 {
+            assert_eq!(hashmap.get(&i), Some(&(i * 2)));
+        }
+    }
+
+    #[test]
+    fn get_and_remove_accept_borrowed_keys() {
+        let mut hashmap: HashMap<String, i32> = HashMap::new();
+        hashmap.insert(String::from("guimauve"), 1);
+
+        assert_eq!(hashmap.get("guimauve"), Some(&1));
+        assert_eq!(hashmap.remove("guimauve"), Some(1));
+        assert_eq!(hashmap.get("guimauve"), None);
+    }
+
+    #[test]
+    fn remove_then_reinsert_reuses_tombstones() {
+        let mut hashmap: HashMap<i32, i32> = HashMap::with_capacity(4);
+        for i in 0..20 {
+            hashmap.insert(i, i);
+            hashmap.remove(&i);
+        }
+        assert_eq!(hashmap.curr_size, 0);
+        hashmap.insert(1, 1);
+        assert_eq!(hashmap.get(&1), Some(&1));
+    }
+
     //#[test]
     //fn delete_key_value() {
     //    let (key, value) = ("guimauve", 1);